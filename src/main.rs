@@ -2,13 +2,16 @@ use std::fmt::{self, Display, Formatter};
 
 use iced::{
     executor, mouse,
+    widget::canvas::{self, Frame, Geometry, Path, Program, Stroke},
     widget::{
-        button,
-        canvas::{Frame, Geometry, Path, Program, Stroke},
-        column, container, horizontal_space, row, text, vertical_space, Canvas,
+        button, column, container, horizontal_space, row, text, text_input, vertical_space,
+        Canvas,
     },
-    window, Application, Command, Rectangle, Renderer, Settings, Theme,
+    window, Application, Command, Point, Rectangle, Renderer, Settings, Size, Theme,
 };
+use rand::seq::SliceRandom;
+use rodio::{Decoder, Sink};
+use serde::{Deserialize, Serialize};
 
 fn main() -> iced::Result {
     TicTacToe::run(Settings {
@@ -22,7 +25,15 @@ fn main() -> iced::Result {
     })
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+const DEFAULT_BOARD_SIZE: usize = 3;
+const DEFAULT_WIN_LENGTH: usize = 3;
+const SAVE_FILE_PATH: &str = "tictactoe_save.cbor";
+const CLICK_SOUND_PATH: &str = "assets/sounds/click.wav";
+const BUZZ_SOUND_PATH: &str = "assets/sounds/buzz.wav";
+const VICTORY_SOUND_PATH: &str = "assets/sounds/victory.wav";
+const CELL_SIZE: f32 = 100.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 enum SquareValue {
     X,
     O,
@@ -46,24 +57,263 @@ impl Display for SquareValue {
     }
 }
 
-type SquareArray = [Option<SquareValue>; 9];
+type SquareArray = Vec<Option<SquareValue>>;
+
+fn empty_board(n: usize) -> SquareArray {
+    vec![None; n * n]
+}
+
+/// The subset of `TicTacToe`'s state needed to resume a game, including its
+/// full turn history so time-travel navigation survives a save/load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedGame {
+    n: usize,
+    win_len: usize,
+    next_square_value: SquareValue,
+    winner: Option<SquareValue>,
+    turns: Vec<SquareArray>,
+    turn_index: usize,
+}
+
+fn save_game(saved_game: SavedGame) -> Result<(), String> {
+    let bytes = serde_cbor::to_vec(&saved_game).map_err(|error| error.to_string())?;
+    std::fs::write(SAVE_FILE_PATH, bytes).map_err(|error| error.to_string())
+}
+
+fn load_game() -> Result<SavedGame, String> {
+    let bytes = std::fs::read(SAVE_FILE_PATH).map_err(|error| error.to_string())?;
+    let saved_game: SavedGame = serde_cbor::from_slice(&bytes).map_err(|error| error.to_string())?;
+
+    if saved_game.turns.is_empty() {
+        return Err("save file has no turns".to_string());
+    }
+    if saved_game.turn_index >= saved_game.turns.len() {
+        return Err("save file's turn_index is out of bounds".to_string());
+    }
+    let expected_len = saved_game.n * saved_game.n;
+    if saved_game.turns.iter().any(|turn| turn.len() != expected_len) {
+        return Err("save file's board size doesn't match its turns".to_string());
+    }
+
+    Ok(saved_game)
+}
+
+/// Sound clips preloaded once at startup so playback never touches disk.
+struct Assets {
+    click: Vec<u8>,
+    buzz: Vec<u8>,
+    victory: Vec<u8>,
+}
+
+impl Assets {
+    fn load() -> Self {
+        Assets {
+            click: std::fs::read(CLICK_SOUND_PATH).unwrap_or_default(),
+            buzz: std::fs::read(BUZZ_SOUND_PATH).unwrap_or_default(),
+            victory: std::fs::read(VICTORY_SOUND_PATH).unwrap_or_default(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
+enum SoundKind {
+    Click,
+    Buzz,
+    Victory,
+}
+
+/// Plays a clip on the system's default output device, blocking until it
+/// finishes. Runs inside a `Command::perform` so it never blocks the UI.
+fn play_sound(bytes: Vec<u8>) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    let Ok((_stream, handle)) = rodio::OutputStream::try_default() else {
+        return;
+    };
+    let Ok(sink) = Sink::try_new(&handle) else {
+        return;
+    };
+    let Ok(source) = Decoder::new(std::io::Cursor::new(bytes)) else {
+        return;
+    };
+
+    sink.append(source);
+    sink.sleep_until_end();
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum GameMode {
+    HumanVsHuman,
+    HumanVsAi,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Caps minimax recursion depth. Lower caps make the AI shortsighted,
+    /// which is what makes "Easy" actually easy to beat. "Hard" only gets an
+    /// exhaustive search on a standard 3x3 board: without move ordering or a
+    /// transposition table, a full search of a larger board (e.g. 5x5) would
+    /// make the AI's move effectively never return.
+    fn max_depth(&self, n: usize) -> usize {
+        let cap = match self {
+            Difficulty::Easy => 2,
+            Difficulty::Medium => 4,
+            Difficulty::Hard if n <= 3 => usize::MAX,
+            Difficulty::Hard => 6,
+        };
+
+        cap.min(n * n)
+    }
+
+    /// How many of the top-scoring moves to randomly choose among.
+    fn near_optimal_count(&self) -> usize {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Medium => 2,
+            Difficulty::Hard => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 enum Message {
     SquareClicked(usize),
     PreviousTurn,
     NextTurn,
     StartNewGame,
+    BoardSizeSelected(usize),
+    WinLengthSelected(usize),
+    GameModeSelected(GameMode),
+    DifficultySelected(Difficulty),
+    AiMoveCalculated(Option<usize>),
+    SaveGame,
+    GameSaved(Result<(), String>),
+    LoadGame,
+    GameLoaded(Result<SavedGame, String>),
+    ToggleSound,
+    SoundPlayed,
+    PlayerXNameChanged(String),
+    PlayerONameChanged(String),
+    ResetMatch,
 }
 
 struct TicTacToe {
+    n: usize,
+    win_len: usize,
     next_square_value: SquareValue,
     winner: Option<SquareValue>,
+    is_draw: bool,
     turns: Vec<SquareArray>,
     turn_index: usize,
+    game_mode: GameMode,
+    ai_player: SquareValue,
+    difficulty: Difficulty,
+    io_message: Option<String>,
+    assets: Assets,
+    sound_enabled: bool,
+    player_x_name: String,
+    player_o_name: String,
+    scores: (u32, u32, u32),
+}
+
+impl TicTacToe {
+    /// Places `self.next_square_value` at `square_index` on the current
+    /// turn's board if the square is empty and the game isn't already won,
+    /// recording a new turn and advancing whose turn it is. Returns whether
+    /// the move was applied.
+    fn place_square(&mut self, square_index: usize) -> bool {
+        let mut next_squares = self.turns[self.turn_index].clone();
+        let square = next_squares[square_index];
+        if self.winner.is_some()
+            || square.is_some()
+            || calculate_winner(&next_squares, self.n, self.win_len).is_some()
+        {
+            return false;
+        }
+
+        next_squares[square_index] = Some(self.next_square_value);
+
+        if self.turn_index + 1 < self.turns.len() {
+            self.turns[self.turn_index + 1] = next_squares.clone();
+            self.turns.truncate(self.turn_index + 2);
+        } else {
+            self.turns.push(next_squares.clone());
+        }
+
+        self.turn_index += 1;
+        self.next_square_value = self.next_square_value.next();
+        self.winner = calculate_winner(&next_squares, self.n, self.win_len);
+        self.is_draw = self.winner.is_none() && next_squares.iter().all(Option::is_some);
+
+        match self.winner {
+            Some(SquareValue::X) => self.scores.0 += 1,
+            Some(SquareValue::O) => self.scores.1 += 1,
+            None if self.is_draw => self.scores.2 += 1,
+            None => {}
+        }
+
+        true
+    }
+
+    /// The name to display for a player: their chosen name, or their square
+    /// value if they haven't entered one.
+    fn player_name(&self, value: SquareValue) -> &str {
+        let name = match value {
+            SquareValue::X => &self.player_x_name,
+            SquareValue::O => &self.player_o_name,
+        };
+
+        if name.is_empty() {
+            match value {
+                SquareValue::X => "X",
+                SquareValue::O => "O",
+            }
+        } else {
+            name
+        }
+    }
+
+    /// Dispatches playback of the given clip as a `Command`, or `Command::none()`
+    /// if sound is muted.
+    fn play_sound_command(&self, kind: SoundKind) -> Command<Message> {
+        if !self.sound_enabled {
+            return Command::none();
+        }
+
+        let bytes = match kind {
+            SoundKind::Click => self.assets.click.clone(),
+            SoundKind::Buzz => self.assets.buzz.clone(),
+            SoundKind::Victory => self.assets.victory.clone(),
+        };
+
+        Command::perform(
+            async move {
+                // `play_sound` blocks the calling thread until the clip finishes, so it
+                // must run on a blocking-pool thread rather than this async task's
+                // tokio executor thread.
+                let _ = tokio::task::spawn_blocking(move || play_sound(bytes)).await;
+            },
+            |_| Message::SoundPlayed,
+        )
+    }
 }
 
 impl Application for TicTacToe {
+    // `executor::Default` only runs futures if `iced`'s Cargo.toml feature
+    // for an async runtime (e.g. `tokio`, `async-std`, `smol`, or
+    // `thread-pool`) is enabled; otherwise it silently falls back to a
+    // null executor that drops every `Command::perform` future instead of
+    // running it. The AI opponent, save/load, and sound playback all rely
+    // on `Command::perform` completing, so one of those features must be
+    // on in Cargo.toml or their callback messages will never arrive.
     type Executor = executor::Default;
     type Message = Message;
     type Theme = Theme;
@@ -72,10 +322,22 @@ impl Application for TicTacToe {
     fn new(_flags: Self::Flags) -> (Self, Command<Message>) {
         (
             TicTacToe {
+                n: DEFAULT_BOARD_SIZE,
+                win_len: DEFAULT_WIN_LENGTH,
                 next_square_value: SquareValue::X,
                 winner: None,
-                turns: vec![[None::<SquareValue>; 9]],
+                is_draw: false,
+                turns: vec![empty_board(DEFAULT_BOARD_SIZE)],
                 turn_index: 0,
+                game_mode: GameMode::HumanVsHuman,
+                ai_player: SquareValue::O,
+                difficulty: Difficulty::Medium,
+                io_message: None,
+                assets: Assets::load(),
+                sound_enabled: true,
+                player_x_name: String::new(),
+                player_o_name: String::new(),
+                scores: (0, 0, 0),
             },
             iced::Command::none(),
         )
@@ -92,27 +354,40 @@ impl Application for TicTacToe {
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::SquareClicked(square_index) => {
-                let mut next_squares = self.turns[self.turn_index];
-                let square = next_squares[square_index];
-                if self.winner.is_some()
-                    || square.is_some()
-                    || calculate_winner(&next_squares).is_some()
-                {
-                    return iced::Command::none();
+                if !self.place_square(square_index) {
+                    return self.play_sound_command(SoundKind::Buzz);
                 }
 
-                next_squares[square_index] = Some(self.next_square_value);
+                let mut commands = vec![self.play_sound_command(SoundKind::Click)];
+                if self.winner.is_some() {
+                    commands.push(self.play_sound_command(SoundKind::Victory));
+                }
 
-                if self.turn_index + 1 < self.turns.len() {
-                    self.turns[self.turn_index + 1] = next_squares;
-                    self.turns.truncate(self.turn_index + 2);
-                } else {
-                    self.turns.push(next_squares);
+                if self.game_mode == GameMode::HumanVsAi
+                    && self.winner.is_none()
+                    && self.next_square_value == self.ai_player
+                {
+                    let squares = self.turns[self.turn_index].clone();
+                    let n = self.n;
+                    let win_len = self.win_len;
+                    let ai_player = self.ai_player;
+                    let difficulty = self.difficulty;
+                    commands.push(Command::perform(
+                        async move { best_ai_move(squares, n, win_len, ai_player, difficulty) },
+                        Message::AiMoveCalculated,
+                    ));
                 }
 
-                self.turn_index += 1;
-                self.next_square_value = self.next_square_value.next();
-                self.winner = calculate_winner(&next_squares);
+                return Command::batch(commands);
+            }
+            Message::AiMoveCalculated(square_index) => {
+                if square_index.is_some_and(|square_index| self.place_square(square_index)) {
+                    let mut commands = vec![self.play_sound_command(SoundKind::Click)];
+                    if self.winner.is_some() {
+                        commands.push(self.play_sound_command(SoundKind::Victory));
+                    }
+                    return Command::batch(commands);
+                }
             }
             Message::PreviousTurn => {
                 if self.turn_index == 0 {
@@ -133,8 +408,94 @@ impl Application for TicTacToe {
             Message::StartNewGame => {
                 self.turn_index = 0;
                 self.winner = None;
+                self.is_draw = false;
+                self.next_square_value = SquareValue::X;
+                self.turns = vec![empty_board(self.n)];
+            }
+            Message::BoardSizeSelected(n) => {
+                self.n = n;
+                self.win_len = self.win_len.min(n);
+                self.turn_index = 0;
+                self.winner = None;
+                self.is_draw = false;
+                self.next_square_value = SquareValue::X;
+                self.turns = vec![empty_board(self.n)];
+            }
+            Message::WinLengthSelected(win_len) => {
+                self.win_len = win_len.min(self.n);
+                self.turn_index = 0;
+                self.winner = None;
+                self.is_draw = false;
+                self.next_square_value = SquareValue::X;
+                self.turns = vec![empty_board(self.n)];
+            }
+            Message::GameModeSelected(game_mode) => {
+                self.game_mode = game_mode;
+                self.turn_index = 0;
+                self.winner = None;
+                self.is_draw = false;
                 self.next_square_value = SquareValue::X;
-                self.turns = vec![[None::<SquareValue>; 9]];
+                self.turns = vec![empty_board(self.n)];
+            }
+            Message::DifficultySelected(difficulty) => {
+                self.difficulty = difficulty;
+            }
+            Message::SaveGame => {
+                let saved_game = SavedGame {
+                    n: self.n,
+                    win_len: self.win_len,
+                    next_square_value: self.next_square_value,
+                    winner: self.winner,
+                    turns: self.turns.clone(),
+                    turn_index: self.turn_index,
+                };
+                return Command::perform(
+                    async move { save_game(saved_game) },
+                    Message::GameSaved,
+                );
+            }
+            Message::GameSaved(result) => {
+                self.io_message = Some(match result {
+                    Ok(()) => "Game saved".to_string(),
+                    Err(error) => format!("Failed to save game: {error}"),
+                });
+            }
+            Message::LoadGame => {
+                return Command::perform(async { load_game() }, Message::GameLoaded);
+            }
+            Message::GameLoaded(result) => match result {
+                Ok(saved_game) => {
+                    self.n = saved_game.n;
+                    self.win_len = saved_game.win_len;
+                    self.next_square_value = saved_game.next_square_value;
+                    self.winner = saved_game.winner;
+                    self.turns = saved_game.turns;
+                    self.turn_index = saved_game.turn_index;
+                    self.is_draw = self.winner.is_none()
+                        && self.turns[self.turn_index].iter().all(Option::is_some);
+                    self.io_message = Some("Game loaded".to_string());
+                }
+                Err(error) => {
+                    self.io_message = Some(format!("Failed to load game: {error}"));
+                }
+            },
+            Message::ToggleSound => {
+                self.sound_enabled = !self.sound_enabled;
+            }
+            Message::SoundPlayed => {}
+            Message::PlayerXNameChanged(name) => {
+                self.player_x_name = name;
+            }
+            Message::PlayerONameChanged(name) => {
+                self.player_o_name = name;
+            }
+            Message::ResetMatch => {
+                self.scores = (0, 0, 0);
+                self.turn_index = 0;
+                self.winner = None;
+                self.is_draw = false;
+                self.next_square_value = SquareValue::X;
+                self.turns = vec![empty_board(self.n)];
             }
         }
 
@@ -148,149 +509,497 @@ impl Application for TicTacToe {
             button("→").on_press(Message::NextTurn),
             horizontal_space(2),
             button("Start new game").on_press(Message::StartNewGame),
+            horizontal_space(10),
+            button("Save game").on_press(Message::SaveGame),
+            horizontal_space(2),
+            button("Load game").on_press(Message::LoadGame),
+            horizontal_space(10),
+            button(if self.sound_enabled { "Mute" } else { "Unmute" }).on_press(Message::ToggleSound),
+            horizontal_space(10),
+            button("3x3").on_press(Message::BoardSizeSelected(3)),
+            horizontal_space(2),
+            button("5x5").on_press(Message::BoardSizeSelected(5)),
+            horizontal_space(10),
+            button("3 in a row").on_press(Message::WinLengthSelected(3)),
+            horizontal_space(2),
+            button("4 in a row").on_press(Message::WinLengthSelected(4)),
         ];
 
-        let mut board_buttons =
-            self.turns[self.turn_index]
-                .iter()
-                .enumerate()
-                .map(|(square_index, &square)| {
-                    button(Canvas::new(Square { value: square }))
-                        .width(100)
-                        .height(100)
-                        .on_press(Message::SquareClicked(square_index))
-                });
+        let ai_settings = row![
+            button("vs Human").on_press(Message::GameModeSelected(GameMode::HumanVsHuman)),
+            horizontal_space(2),
+            button("vs AI").on_press(Message::GameModeSelected(GameMode::HumanVsAi)),
+            horizontal_space(10),
+            button("Easy").on_press(Message::DifficultySelected(Difficulty::Easy)),
+            horizontal_space(2),
+            button("Medium").on_press(Message::DifficultySelected(Difficulty::Medium)),
+            horizontal_space(2),
+            button("Hard").on_press(Message::DifficultySelected(Difficulty::Hard)),
+        ];
+
+        let player_names = row![
+            text_input("Player X name", &self.player_x_name)
+                .on_input(Message::PlayerXNameChanged)
+                .width(150),
+            horizontal_space(10),
+            text_input("Player O name", &self.player_o_name)
+                .on_input(Message::PlayerONameChanged)
+                .width(150),
+            horizontal_space(10),
+            button("Reset match").on_press(Message::ResetMatch),
+        ];
+
+        let scoreboard = text(format!(
+            "{}: {}  |  {}: {}  |  Draws: {}",
+            self.player_name(SquareValue::X),
+            self.scores.0,
+            self.player_name(SquareValue::O),
+            self.scores.1,
+            self.scores.2,
+        ));
+
+        let current_squares = &self.turns[self.turn_index];
+
+        let board_size = self.n as f32 * CELL_SIZE;
+        let board = container(
+            Canvas::new(Board {
+                squares: current_squares,
+                n: self.n,
+            })
+            .width(board_size)
+            .height(board_size),
+        );
 
         let status = text(if let Some(winner) = self.winner {
-            format!("Player {} won!", winner)
+            format!("{} won!", self.player_name(winner))
+        } else if self.is_draw {
+            "It's a draw!".to_string()
         } else {
-            format!("It's {}'s turn", self.next_square_value)
+            format!("It's {}'s turn", self.player_name(self.next_square_value))
         });
 
-        let board = container(row![column![
-            row![
-                board_buttons.next().unwrap(),
-                horizontal_space(5),
-                board_buttons.next().unwrap(),
-                horizontal_space(5),
-                board_buttons.next().unwrap(),
-            ],
-            vertical_space(5),
-            row![
-                board_buttons.next().unwrap(),
-                horizontal_space(5),
-                board_buttons.next().unwrap(),
-                horizontal_space(5),
-                board_buttons.next().unwrap(),
-            ],
-            vertical_space(5),
-            row![
-                board_buttons.next().unwrap(),
-                horizontal_space(5),
-                board_buttons.next().unwrap(),
-                horizontal_space(5),
-                board_buttons.next().unwrap(),
-            ],
-        ]]);
-
-        let content = column![
+        let mut content = column![
             "Tic Tac Toe!",
             vertical_space(10),
             actions,
             vertical_space(10),
+            ai_settings,
+            vertical_space(10),
+            player_names,
+            vertical_space(10),
             status,
             vertical_space(10),
+            scoreboard,
+            vertical_space(10),
             board,
         ];
+
+        if let Some(io_message) = &self.io_message {
+            content = content.push(vertical_space(10)).push(text(io_message));
+        }
+
         container(content).padding(20).into()
     }
 }
 
-struct Square {
-    value: Option<SquareValue>,
+/// Renders the whole `n`×`n` board as one `Canvas` and hit-tests clicks
+/// against it directly, instead of relying on a grid of separate buttons.
+struct Board<'a> {
+    squares: &'a SquareArray,
+    n: usize,
 }
 
-impl Program<Message> for Square {
+impl<'a> Board<'a> {
+    fn hovered_index(&self, bounds: Rectangle, cursor: mouse::Cursor) -> Option<usize> {
+        let position = cursor.position_in(bounds)?;
+        let cell_size = bounds.width / self.n as f32;
+        let col = (position.x / cell_size) as usize;
+        let row = (position.y / cell_size) as usize;
+        if row >= self.n || col >= self.n {
+            return None;
+        }
+
+        Some(row * self.n + col)
+    }
+}
+
+impl<'a> Program<Message> for Board<'a> {
     type State = ();
 
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (canvas::event::Status, Option<Message>) {
+        let canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return (canvas::event::Status::Ignored, None);
+        };
+
+        match self.hovered_index(bounds, cursor) {
+            Some(square_index) => (
+                canvas::event::Status::Captured,
+                Some(Message::SquareClicked(square_index)),
+            ),
+            None => (canvas::event::Status::Ignored, None),
+        }
+    }
+
     fn draw(
         &self,
         _state: &(),
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
-        // We prepare a new `Frame`
         let mut frame = Frame::new(renderer, bounds.size());
+        let cell_size = bounds.width / self.n as f32;
+
+        if let Some(hovered_index) = self.hovered_index(bounds, cursor) {
+            let row = hovered_index / self.n;
+            let col = hovered_index % self.n;
+            frame.fill_rectangle(
+                Point::new(col as f32 * cell_size, row as f32 * cell_size),
+                Size::new(cell_size, cell_size),
+                iced::Color::from_rgba(1.0, 1.0, 1.0, 0.08),
+            );
+        }
 
-        let stroke_width = 3.0;
-        let padding = 3.0;
-
-        match self.value {
-            Some(SquareValue::X) => {
-                frame.stroke(
-                    &Path::line(
-                        frame.center()
-                            - iced::Vector::new(
-                                bounds.width / 2.0 - padding,
-                                bounds.height / 2.0 - padding,
-                            ),
-                        frame.center()
-                            + iced::Vector::new(
-                                bounds.width / 2.0 - padding,
-                                bounds.height / 2.0 - padding,
-                            ),
-                    ),
-                    Stroke::default().with_width(stroke_width),
-                );
-                frame.stroke(
-                    &Path::line(
-                        frame.center()
-                            + iced::Vector::new(
-                                -bounds.width / 2.0 + padding,
-                                bounds.height / 2.0 - padding,
-                            ),
-                        frame.center()
-                            + iced::Vector::new(
-                                bounds.width / 2.0 - padding,
-                                -bounds.height / 2.0 + padding,
-                            ),
-                    ),
-                    Stroke::default().with_width(stroke_width),
-                );
-            }
-            Some(SquareValue::O) => {
-                frame.stroke(
-                    &Path::circle(frame.center(), bounds.size().width / 2.0 - padding),
-                    Stroke::default().with_width(stroke_width),
-                );
-            }
-            None => {}
+        for line_index in 1..self.n {
+            let offset = line_index as f32 * cell_size;
+            frame.stroke(
+                &Path::line(Point::new(offset, 0.0), Point::new(offset, bounds.height)),
+                Stroke::default().with_width(1.0),
+            );
+            frame.stroke(
+                &Path::line(Point::new(0.0, offset), Point::new(bounds.width, offset)),
+                Stroke::default().with_width(1.0),
+            );
+        }
+
+        for (square_index, square) in self.squares.iter().enumerate() {
+            let row = square_index / self.n;
+            let col = square_index % self.n;
+            let cell_bounds = Rectangle {
+                x: col as f32 * cell_size,
+                y: row as f32 * cell_size,
+                width: cell_size,
+                height: cell_size,
+            };
+            draw_square_mark(&mut frame, cell_bounds, *square);
         }
 
         vec![frame.into_geometry()]
     }
 }
 
-fn calculate_winner(squares: &SquareArray) -> Option<SquareValue> {
-    let lines = [
-        [0, 1, 2],
-        [3, 4, 5],
-        [6, 7, 8],
-        [0, 3, 6],
-        [1, 4, 7],
-        [2, 5, 8],
-        [0, 4, 8],
-        [2, 4, 6],
-    ];
-
-    for line in &lines {
-        let [a, b, c] = line;
-        if squares[*a].is_some() && squares[*a] == squares[*b] && squares[*a] == squares[*c] {
-            return squares[*a];
+fn draw_square_mark(frame: &mut Frame, cell_bounds: Rectangle, value: Option<SquareValue>) {
+    let stroke_width = 3.0;
+    let padding = 3.0;
+    let center = Point::new(
+        cell_bounds.x + cell_bounds.width / 2.0,
+        cell_bounds.y + cell_bounds.height / 2.0,
+    );
+
+    match value {
+        Some(SquareValue::X) => {
+            frame.stroke(
+                &Path::line(
+                    center
+                        - iced::Vector::new(
+                            cell_bounds.width / 2.0 - padding,
+                            cell_bounds.height / 2.0 - padding,
+                        ),
+                    center
+                        + iced::Vector::new(
+                            cell_bounds.width / 2.0 - padding,
+                            cell_bounds.height / 2.0 - padding,
+                        ),
+                ),
+                Stroke::default().with_width(stroke_width),
+            );
+            frame.stroke(
+                &Path::line(
+                    center
+                        + iced::Vector::new(
+                            -cell_bounds.width / 2.0 + padding,
+                            cell_bounds.height / 2.0 - padding,
+                        ),
+                    center
+                        + iced::Vector::new(
+                            cell_bounds.width / 2.0 - padding,
+                            -cell_bounds.height / 2.0 + padding,
+                        ),
+                ),
+                Stroke::default().with_width(stroke_width),
+            );
+        }
+        Some(SquareValue::O) => {
+            frame.stroke(
+                &Path::circle(center, cell_bounds.width / 2.0 - padding),
+                Stroke::default().with_width(stroke_width),
+            );
+        }
+        None => {}
+    }
+}
+
+/// Scans every cell of an `n`×`n` board as the start of a run in each of the
+/// four directions (right, down, down-right, down-left), looking for
+/// `win_len` consecutive equal, non-empty cells.
+fn calculate_winner(squares: &SquareArray, n: usize, win_len: usize) -> Option<SquareValue> {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+    for row in 0..n {
+        for col in 0..n {
+            let Some(value) = squares[row * n + col] else {
+                continue;
+            };
+
+            for (d_row, d_col) in DIRECTIONS {
+                let end_row = row as isize + d_row * (win_len as isize - 1);
+                let end_col = col as isize + d_col * (win_len as isize - 1);
+                if end_row < 0 || end_row >= n as isize || end_col < 0 || end_col >= n as isize {
+                    continue;
+                }
+
+                let is_run = (1..win_len).all(|step| {
+                    let r = (row as isize + d_row * step as isize) as usize;
+                    let c = (col as isize + d_col * step as isize) as usize;
+                    squares[r * n + c] == Some(value)
+                });
+
+                if is_run {
+                    return Some(value);
+                }
+            }
         }
     }
 
     None
 }
+
+/// Bundles the search parameters that stay constant across a single
+/// `best_ai_move` call, so `minimax` doesn't need to take them individually.
+#[derive(Copy, Clone)]
+struct SearchConfig {
+    n: usize,
+    win_len: usize,
+    ai_player: SquareValue,
+    max_depth: usize,
+}
+
+/// Minimax with alpha-beta pruning. `side_to_move` is the player about to
+/// move; the returned score is always from `config.ai_player`'s perspective:
+/// +10 minus depth for an AI win, -10 plus depth for a human win, 0 for a
+/// draw or for a cutoff once `config.max_depth` is reached.
+fn minimax(
+    squares: &mut SquareArray,
+    config: SearchConfig,
+    side_to_move: SquareValue,
+    depth: usize,
+    mut alpha: i32,
+    mut beta: i32,
+) -> i32 {
+    if let Some(winner) = calculate_winner(squares, config.n, config.win_len) {
+        return if winner == config.ai_player {
+            10 - depth as i32
+        } else {
+            depth as i32 - 10
+        };
+    }
+
+    let empty_indices: Vec<usize> = squares
+        .iter()
+        .enumerate()
+        .filter(|(_, square)| square.is_none())
+        .map(|(index, _)| index)
+        .collect();
+
+    if empty_indices.is_empty() || depth >= config.max_depth {
+        return 0;
+    }
+
+    if side_to_move == config.ai_player {
+        let mut best_score = i32::MIN;
+        for index in empty_indices {
+            squares[index] = Some(side_to_move);
+            let score = minimax(squares, config, side_to_move.next(), depth + 1, alpha, beta);
+            squares[index] = None;
+
+            best_score = best_score.max(score);
+            alpha = alpha.max(best_score);
+            if beta <= alpha {
+                break;
+            }
+        }
+        best_score
+    } else {
+        let mut best_score = i32::MAX;
+        for index in empty_indices {
+            squares[index] = Some(side_to_move);
+            let score = minimax(squares, config, side_to_move.next(), depth + 1, alpha, beta);
+            squares[index] = None;
+
+            best_score = best_score.min(score);
+            beta = beta.min(best_score);
+            if beta <= alpha {
+                break;
+            }
+        }
+        best_score
+    }
+}
+
+/// Picks a move for `ai_player` on `squares`, scoring every empty square with
+/// minimax and randomly choosing among the top `difficulty.near_optimal_count()`
+/// moves. Returns `None` if the board is already full.
+fn best_ai_move(
+    mut squares: SquareArray,
+    n: usize,
+    win_len: usize,
+    ai_player: SquareValue,
+    difficulty: Difficulty,
+) -> Option<usize> {
+    let empty_indices: Vec<usize> = squares
+        .iter()
+        .enumerate()
+        .filter(|(_, square)| square.is_none())
+        .map(|(index, _)| index)
+        .collect();
+
+    if empty_indices.is_empty() {
+        return None;
+    }
+
+    let config = SearchConfig {
+        n,
+        win_len,
+        ai_player,
+        max_depth: difficulty.max_depth(n),
+    };
+    let mut scored_moves: Vec<(usize, i32)> = empty_indices
+        .into_iter()
+        .map(|index| {
+            squares[index] = Some(ai_player);
+            let score = minimax(&mut squares, config, ai_player.next(), 1, i32::MIN, i32::MAX);
+            squares[index] = None;
+            (index, score)
+        })
+        .collect();
+
+    scored_moves.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored_moves.truncate(difficulty.near_optimal_count().min(scored_moves.len()));
+
+    scored_moves
+        .choose(&mut rand::thread_rng())
+        .map(|&(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(n: usize, marks: &[(usize, usize, SquareValue)]) -> SquareArray {
+        let mut squares = empty_board(n);
+        for &(row, col, value) in marks {
+            squares[row * n + col] = Some(value);
+        }
+        squares
+    }
+
+    #[test]
+    fn calculate_winner_detects_horizontal_win_on_3x3() {
+        let squares = board(
+            3,
+            &[
+                (0, 0, SquareValue::X),
+                (0, 1, SquareValue::X),
+                (0, 2, SquareValue::X),
+            ],
+        );
+        assert_eq!(calculate_winner(&squares, 3, 3), Some(SquareValue::X));
+    }
+
+    #[test]
+    fn calculate_winner_detects_vertical_win_on_3x3() {
+        let squares = board(
+            3,
+            &[
+                (0, 1, SquareValue::O),
+                (1, 1, SquareValue::O),
+                (2, 1, SquareValue::O),
+            ],
+        );
+        assert_eq!(calculate_winner(&squares, 3, 3), Some(SquareValue::O));
+    }
+
+    #[test]
+    fn calculate_winner_detects_anti_diagonal_win_on_5x5_four_in_a_row() {
+        let squares = board(
+            5,
+            &[
+                (0, 3, SquareValue::X),
+                (1, 2, SquareValue::X),
+                (2, 1, SquareValue::X),
+                (3, 0, SquareValue::X),
+            ],
+        );
+        assert_eq!(calculate_winner(&squares, 5, 4), Some(SquareValue::X));
+    }
+
+    #[test]
+    fn calculate_winner_ignores_a_three_run_when_win_len_is_four() {
+        let squares = board(
+            5,
+            &[
+                (0, 0, SquareValue::O),
+                (0, 1, SquareValue::O),
+                (0, 2, SquareValue::O),
+            ],
+        );
+        assert_eq!(calculate_winner(&squares, 5, 4), None);
+    }
+
+    #[test]
+    fn calculate_winner_returns_none_on_empty_board() {
+        assert_eq!(calculate_winner(&empty_board(3), 3, 3), None);
+    }
+
+    #[test]
+    fn best_ai_move_takes_an_immediate_winning_move() {
+        // X X _
+        // O O _
+        // _ _ _
+        let squares = board(
+            3,
+            &[
+                (0, 0, SquareValue::X),
+                (0, 1, SquareValue::X),
+                (1, 0, SquareValue::O),
+                (1, 1, SquareValue::O),
+            ],
+        );
+        let chosen = best_ai_move(squares, 3, 3, SquareValue::X, Difficulty::Hard);
+        assert_eq!(chosen, Some(2));
+    }
+
+    #[test]
+    fn best_ai_move_blocks_an_immediate_opponent_win() {
+        // O O _
+        // X _ _
+        // _ _ _
+        let squares = board(
+            3,
+            &[
+                (0, 0, SquareValue::O),
+                (0, 1, SquareValue::O),
+                (1, 0, SquareValue::X),
+            ],
+        );
+        let chosen = best_ai_move(squares, 3, 3, SquareValue::X, Difficulty::Hard);
+        assert_eq!(chosen, Some(2));
+    }
+}